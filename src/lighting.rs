@@ -1,38 +1,149 @@
+use std::cell::Cell;
+
 use pyo3::prelude::*;
 
 pub type LightId = u32;
 
+/// Kind of light source, determining how its contribution is computed
+#[derive(Debug, Clone)]
+pub enum LightKind {
+    /// Omnidirectional light falling off with distance from its position
+    Point,
+    /// Cone-shaped light aimed along `direction`, fading between `inner_angle`
+    /// and `outer_angle` (both in radians, measured from the direction vector)
+    Spot {
+        direction: [f32; 2],
+        inner_angle: f32,
+        outer_angle: f32,
+    },
+    /// Uniform light with no position or distance falloff, shining along `direction`
+    Directional { direction: [f32; 2] },
+}
+
+/// Falloff curve used to turn distance-to-light into an attenuation factor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AttenuationModel {
+    /// Ad-hoc `(1 - d/radius)^2` ramp; cheap and fully zero at `radius`
+    Smoothstep,
+    /// Real inverse-square falloff windowed to reach zero at `radius`
+    InverseSquare,
+}
+
+impl Default for AttenuationModel {
+    fn default() -> Self {
+        AttenuationModel::Smoothstep
+    }
+}
+
 /// Point light structure
 #[derive(Debug, Clone)]
 pub struct Light {
+    pub kind: LightKind,
     pub radius: f32,
     pub color: [u8; 4],       // RGBA color (0-255)
     pub intensity: f32,        // Light intensity multiplier (default 1.0)
     pub flicker_amount: f32,   // Flicker amount (0.0 = no flicker)
     pub flicker_speed: f32,    // Flicker speed
+    pub enabled: bool,         // Whether the light currently contributes (default true)
+    // Last known render position, for spatial queries like `lights_in_region`.
+    // `Cell` lets `sync_light_positions`/`build_light_grid` update this once per
+    // frame through a shared `&Light`, since the engine supplies the authoritative
+    // (x, y) alongside the light each frame rather than storing it on `Light` itself.
+    pub x: Cell<f32>,
+    pub y: Cell<f32>,
 }
 
 impl Light {
     pub fn new(radius: f32, color: [u8; 4]) -> Self {
         Self {
+            kind: LightKind::Point,
             radius,
             color,
             intensity: 1.0,
             flicker_amount: 0.0,
             flicker_speed: 1.0,
+            enabled: true,
+            x: Cell::new(0.0),
+            y: Cell::new(0.0),
+        }
+    }
+
+    pub fn new_spot(
+        radius: f32,
+        color: [u8; 4],
+        direction: [f32; 2],
+        inner_angle: f32,
+        outer_angle: f32,
+    ) -> Self {
+        Self {
+            kind: LightKind::Spot {
+                direction,
+                inner_angle,
+                outer_angle,
+            },
+            ..Self::new(radius, color)
         }
     }
+
+    pub fn new_directional(color: [u8; 4], direction: [f32; 2]) -> Self {
+        Self {
+            kind: LightKind::Directional { direction },
+            ..Self::new(0.0, color)
+        }
+    }
+}
+
+/// Default side length (in pixels) of a light grid cell
+const DEFAULT_CELL_SIZE: f32 = 192.0;
+
+/// Camera-style exposure controls, mirroring the photographic exposure
+/// triangle (aperture in f-stops, shutter speed in seconds, ISO).
+#[derive(Debug, Clone, Copy)]
+pub struct ExposureSettings {
+    pub aperture: f32,
+    pub shutter_speed: f32,
+    pub iso: f32,
+}
+
+impl Default for ExposureSettings {
+    fn default() -> Self {
+        // Chosen so that `exposure()` evaluates to exactly 1.0, leaving
+        // light intensities unscaled until a caller dials in a real exposure.
+        Self {
+            aperture: 1.0,
+            shutter_speed: 1.2,
+            iso: 100.0,
+        }
+    }
+}
+
+impl ExposureSettings {
+    /// Exposure value normalized to ISO 100
+    pub fn ev100(&self) -> f32 {
+        (self.aperture * self.aperture / self.shutter_speed).log2() - (self.iso / 100.0).log2()
+    }
+
+    /// Scalar multiplier applied to light intensities for this exposure
+    pub fn exposure(&self) -> f32 {
+        1.0 / (2f32.powf(self.ev100()) * 1.2)
+    }
 }
 
 /// Global lighting state
 pub struct LightingState {
     pub ambient: [f32; 4],  // Ambient light color (0.0-1.0)
+    pub cell_size: f32,     // Light grid cell size in pixels, for tiled culling
+    pub attenuation_model: AttenuationModel, // Falloff curve applied to positioned lights
+    pub exposure: ExposureSettings, // Camera-style exposure applied to light intensities
 }
 
 impl Default for LightingState {
     fn default() -> Self {
         Self {
             ambient: [1.0, 1.0, 1.0, 1.0],  // Full white by default (no darkening)
+            cell_size: DEFAULT_CELL_SIZE,
+            attenuation_model: AttenuationModel::Smoothstep,
+            exposure: ExposureSettings::default(),
         }
     }
 }
@@ -43,6 +154,139 @@ impl LightingState {
     }
 }
 
+/// Smooth Hermite interpolation of `x` between `edge0` and `edge1`, clamped to [0, 1]
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let width = edge1 - edge0;
+    if width.abs() < f32::EPSILON {
+        // Zero-width edge (e.g. a hard-edged spotlight with inner_angle == outer_angle):
+        // there's no ramp to interpolate, so fall back to a sign-based step to avoid 0.0/0.0.
+        return if x < edge0 { 0.0 } else { 1.0 };
+    }
+    let t = ((x - edge0) / width).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Attenuation factor for a positioned light at squared distance `dist_sq`
+/// from a sample point, under the given falloff model.
+fn attenuation_for(dist_sq: f32, radius: f32, model: AttenuationModel) -> f32 {
+    match model {
+        AttenuationModel::Smoothstep => {
+            let dist = dist_sq.sqrt();
+            let attenuation = 1.0 - (dist / radius).min(1.0);
+            attenuation * attenuation
+        }
+        AttenuationModel::InverseSquare => {
+            let inv_sq = 1.0 / (dist_sq + 0.01);
+            let window = (1.0 - (dist_sq / (radius * radius)).powi(2))
+                .clamp(0.0, 1.0)
+                .powi(2);
+            inv_sq * window
+        }
+    }
+}
+
+/// Synchronize each light's cached position (`Light::x`/`Light::y`) with the
+/// authoritative per-frame position supplied by the engine, so spatial
+/// queries like `lights_in_region` see where lights actually are. Call this
+/// once per frame before rendering; `build_light_grid` already does this for
+/// callers going through the grid path, so they don't need to call it again.
+pub fn sync_light_positions(lights: &[(f32, f32, &Light)]) {
+    for (lx, ly, light) in lights {
+        light.x.set(*lx);
+        light.y.set(*ly);
+    }
+}
+
+/// Accumulate one light's contribution at `(x, y)` into `total` (r, g, b).
+/// Shared by `calculate_light_contribution` and `calculate_light_contribution_grid`
+/// so the grid path can visit its candidate lights without collecting them first.
+fn accumulate_light(
+    x: f32,
+    y: f32,
+    lx: f32,
+    ly: f32,
+    light: &Light,
+    time: f32,
+    attenuation_model: AttenuationModel,
+    exposure: f32,
+    total: &mut [f32; 3],
+) {
+    if !light.enabled {
+        return;
+    }
+
+    // Apply flicker if enabled
+    let mut intensity = light.intensity;
+    if light.flicker_amount > 0.0 {
+        let flicker_phase = time * light.flicker_speed * 10.0;
+        let flicker = (flicker_phase.sin() * 0.5 + 0.5) * light.flicker_amount;
+        intensity *= 1.0 - flicker;
+    }
+    intensity *= exposure;
+
+    // Convert light color to 0.0-1.0 range
+    let light_r = light.color[0] as f32 / 255.0;
+    let light_g = light.color[1] as f32 / 255.0;
+    let light_b = light.color[2] as f32 / 255.0;
+
+    match &light.kind {
+        LightKind::Directional { .. } => {
+            // No distance attenuation - applies uniformly everywhere
+            total[0] += light_r * intensity;
+            total[1] += light_g * intensity;
+            total[2] += light_b * intensity;
+        }
+        LightKind::Point => {
+            let dx = x - lx;
+            let dy = y - ly;
+            let dist_sq = dx * dx + dy * dy;
+            let radius = light.radius;
+
+            if dist_sq < radius * radius {
+                let attenuation = attenuation_for(dist_sq, radius, attenuation_model);
+
+                total[0] += light_r * attenuation * intensity;
+                total[1] += light_g * attenuation * intensity;
+                total[2] += light_b * attenuation * intensity;
+            }
+        }
+        LightKind::Spot {
+            direction,
+            inner_angle,
+            outer_angle,
+        } => {
+            let dx = x - lx;
+            let dy = y - ly;
+            let dist_sq = dx * dx + dy * dy;
+            let radius = light.radius;
+
+            if dist_sq < radius * radius {
+                let mut attenuation = attenuation_for(dist_sq, radius, attenuation_model);
+
+                // Fade the cone edges between outer_angle and inner_angle
+                let dir_len = (direction[0] * direction[0] + direction[1] * direction[1])
+                    .sqrt()
+                    .max(f32::EPSILON);
+                let dir_x = direction[0] / dir_len;
+                let dir_y = direction[1] / dir_len;
+
+                let dist = dist_sq.sqrt();
+                if dist > f32::EPSILON {
+                    let to_point_x = dx / dist;
+                    let to_point_y = dy / dist;
+                    let cos_angle = to_point_x * dir_x + to_point_y * dir_y;
+                    let cone = smoothstep(outer_angle.cos(), inner_angle.cos(), cos_angle);
+                    attenuation *= cone;
+                }
+
+                total[0] += light_r * attenuation * intensity;
+                total[1] += light_g * attenuation * intensity;
+                total[2] += light_b * attenuation * intensity;
+            }
+        }
+    }
+}
+
 /// Calculate light contribution at a given point
 /// Returns a color multiplier [r, g, b, a] in 0.0-1.0 range
 pub fn calculate_light_contribution(
@@ -51,51 +295,181 @@ pub fn calculate_light_contribution(
     lights: &[(f32, f32, &Light)],  // (x, y, light) tuples
     ambient: [f32; 4],
     time: f32,  // For flicker animation
+    attenuation_model: AttenuationModel,
+    exposure: f32,  // Camera-style exposure multiplier, see `ExposureSettings::exposure`
 ) -> [f32; 4] {
-    // Start with ambient light
-    let mut total_r = ambient[0];
-    let mut total_g = ambient[1];
-    let mut total_b = ambient[2];
+    let mut total = [ambient[0], ambient[1], ambient[2]];
 
-    // Add contribution from each light
     for (lx, ly, light) in lights {
-        let dx = x - lx;
-        let dy = y - ly;
-        let dist_sq = dx * dx + dy * dy;
-        let radius = light.radius;
+        accumulate_light(x, y, *lx, *ly, light, time, attenuation_model, exposure, &mut total);
+    }
 
-        if dist_sq < radius * radius {
-            // Calculate attenuation (inverse square law with smoothing)
-            let dist = dist_sq.sqrt();
-            let attenuation = 1.0 - (dist / radius).min(1.0);
-            let attenuation = attenuation * attenuation; // Quadratic falloff for smoother gradient
-
-            // Apply flicker if enabled
-            let mut intensity = light.intensity;
-            if light.flicker_amount > 0.0 {
-                let flicker_phase = time * light.flicker_speed * 10.0;
-                let flicker = (flicker_phase.sin() * 0.5 + 0.5) * light.flicker_amount;
-                intensity *= 1.0 - flicker;
+    // Clamp to valid range
+    [
+        total[0].min(1.0),
+        total[1].min(1.0),
+        total[2].min(1.0),
+        1.0,  // Alpha stays at 1.0
+    ]
+}
+
+/// Tile-based acceleration structure for light culling, built once per frame.
+///
+/// Positioned lights are bucketed into fixed-size grid cells their bounding
+/// circle overlaps, stored in a flat CSR-style layout (`cell_offsets` +
+/// `indices`) so a point lookup only visits the handful of lights near it
+/// instead of every light in the scene. Directional lights have no position,
+/// so they go in `directional` and apply to every cell.
+pub struct LightGrid {
+    pub cell_size: f32,
+    pub cols: u32,
+    pub rows: u32,
+    pub bounds: (f32, f32, f32, f32), // x, y, width, height
+    pub cell_offsets: Vec<u32>,       // len == cols * rows + 1
+    pub indices: Vec<u32>,            // light indices, grouped by cell
+    pub directional: Vec<u32>,        // indices of directional lights
+}
+
+impl LightGrid {
+    /// Returns the flat cell index containing `(x, y)`, or `None` if outside bounds
+    fn cell_index(&self, x: f32, y: f32) -> Option<usize> {
+        let (bx, by, bw, bh) = self.bounds;
+        if x < bx || y < by || x >= bx + bw || y >= by + bh {
+            return None;
+        }
+        let col = (((x - bx) / self.cell_size) as u32).min(self.cols.saturating_sub(1));
+        let row = (((y - by) / self.cell_size) as u32).min(self.rows.saturating_sub(1));
+        Some((row * self.cols + col) as usize)
+    }
+
+    /// Light indices whose bounding circle overlaps the cell containing `(x, y)`
+    pub fn candidates_at(&self, x: f32, y: f32) -> &[u32] {
+        match self.cell_index(x, y) {
+            Some(cell) => {
+                let start = self.cell_offsets[cell] as usize;
+                let end = self.cell_offsets[cell + 1] as usize;
+                &self.indices[start..end]
+            }
+            None => &[],
+        }
+    }
+}
+
+/// Build a `LightGrid` partitioning `bounds` into `cell_size` cells, assigning
+/// each positioned light to every cell its bounding circle (`x±radius`,
+/// `y±radius`) overlaps. Directional lights carry no position and are instead
+/// collected into `LightGrid::directional`, applied to every cell.
+pub fn build_light_grid(
+    lights: &[(f32, f32, &Light)],
+    bounds: (f32, f32, f32, f32),
+    cell_size: f32,
+) -> LightGrid {
+    let (bx, by, bw, bh) = bounds;
+    let cols = ((bw / cell_size).ceil() as u32).max(1);
+    let rows = ((bh / cell_size).ceil() as u32).max(1);
+    let cell_count = (cols * rows) as usize;
+
+    let cell_range = |lx: f32, ly: f32, radius: f32| -> (u32, u32, u32, u32) {
+        let min_x = (((lx - radius - bx) / cell_size).floor().max(0.0) as u32).min(cols - 1);
+        let max_x = (((lx + radius - bx) / cell_size).floor().max(0.0) as u32).min(cols - 1);
+        let min_y = (((ly - radius - by) / cell_size).floor().max(0.0) as u32).min(rows - 1);
+        let max_y = (((ly + radius - by) / cell_size).floor().max(0.0) as u32).min(rows - 1);
+        (min_x, max_x, min_y, max_y)
+    };
+    let outside_bounds = |lx: f32, ly: f32, radius: f32| -> bool {
+        lx + radius < bx || lx - radius > bx + bw || ly + radius < by || ly - radius > by + bh
+    };
+
+    let mut directional = Vec::new();
+    let mut counts = vec![0u32; cell_count];
+
+    for (i, (lx, ly, light)) in lights.iter().enumerate() {
+        // Once-per-frame position sync, same as `sync_light_positions`.
+        light.x.set(*lx);
+        light.y.set(*ly);
+
+        if !light.enabled {
+            continue;
+        }
+        if matches!(light.kind, LightKind::Directional { .. }) {
+            directional.push(i as u32);
+            continue;
+        }
+        if outside_bounds(*lx, *ly, light.radius) {
+            continue;
+        }
+        let (min_x, max_x, min_y, max_y) = cell_range(*lx, *ly, light.radius);
+        for row in min_y..=max_y {
+            for col in min_x..=max_x {
+                counts[(row * cols + col) as usize] += 1;
             }
+        }
+    }
 
-            // Convert light color to 0.0-1.0 range
-            let light_r = light.color[0] as f32 / 255.0;
-            let light_g = light.color[1] as f32 / 255.0;
-            let light_b = light.color[2] as f32 / 255.0;
+    // Prefix-sum the per-cell counts into CSR offsets
+    let mut cell_offsets = vec![0u32; cell_count + 1];
+    for i in 0..cell_count {
+        cell_offsets[i + 1] = cell_offsets[i] + counts[i];
+    }
 
-            // Add light contribution
-            total_r += light_r * attenuation * intensity;
-            total_g += light_g * attenuation * intensity;
-            total_b += light_b * attenuation * intensity;
+    let mut indices = vec![0u32; cell_offsets[cell_count] as usize];
+    let mut cursors = cell_offsets.clone();
+
+    for (i, (lx, ly, light)) in lights.iter().enumerate() {
+        if !light.enabled
+            || matches!(light.kind, LightKind::Directional { .. })
+            || outside_bounds(*lx, *ly, light.radius)
+        {
+            continue;
+        }
+        let (min_x, max_x, min_y, max_y) = cell_range(*lx, *ly, light.radius);
+        for row in min_y..=max_y {
+            for col in min_x..=max_x {
+                let cell = (row * cols + col) as usize;
+                indices[cursors[cell] as usize] = i as u32;
+                cursors[cell] += 1;
+            }
         }
     }
 
-    // Clamp to valid range
+    LightGrid {
+        cell_size,
+        cols,
+        rows,
+        bounds,
+        cell_offsets,
+        indices,
+        directional,
+    }
+}
+
+/// Like `calculate_light_contribution`, but uses a prebuilt `LightGrid` to only
+/// examine lights whose bounding circle overlaps the cell containing `(x, y)`,
+/// plus any directional lights. O(lights in cell) instead of O(all lights).
+pub fn calculate_light_contribution_grid(
+    x: f32,
+    y: f32,
+    lights: &[(f32, f32, &Light)],
+    grid: &LightGrid,
+    ambient: [f32; 4],
+    time: f32,
+    attenuation_model: AttenuationModel,
+    exposure: f32,
+) -> [f32; 4] {
+    let mut total = [ambient[0], ambient[1], ambient[2]];
+
+    // No intermediate Vec: walk the cell's candidates and the directional list
+    // directly so this stays O(lights near the point) with zero allocation.
+    for &i in grid.candidates_at(x, y).iter().chain(grid.directional.iter()) {
+        let (lx, ly, light) = lights[i as usize];
+        accumulate_light(x, y, lx, ly, light, time, attenuation_model, exposure, &mut total);
+    }
+
     [
-        total_r.min(1.0),
-        total_g.min(1.0),
-        total_b.min(1.0),
-        1.0,  // Alpha stays at 1.0
+        total[0].min(1.0),
+        total[1].min(1.0),
+        total[2].min(1.0),
+        1.0,
     ]
 }
 
@@ -111,6 +485,46 @@ pub fn set_ambient(r: u8, g: u8, b: u8, a: u8) -> PyResult<()> {
     })
 }
 
+#[pyfunction]
+pub fn set_attenuation_model(model: &str) -> PyResult<()> {
+    let model = match model {
+        "smoothstep" => AttenuationModel::Smoothstep,
+        "inverse_square" => AttenuationModel::InverseSquare,
+        other => {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Unknown attenuation model: {} (expected 'smoothstep' or 'inverse_square')",
+                other
+            )))
+        }
+    };
+    crate::engine::with_engine(|engine| {
+        engine.lighting.attenuation_model = model;
+    })
+}
+
+#[pyfunction]
+pub fn set_light_grid_cell_size(cell_size: f32) -> PyResult<()> {
+    if cell_size <= 0.0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "Light grid cell_size must be positive",
+        ));
+    }
+    crate::engine::with_engine(|engine| {
+        engine.lighting.cell_size = cell_size;
+    })
+}
+
+#[pyfunction]
+pub fn set_exposure(aperture: f32, shutter_speed: f32, iso: f32) -> PyResult<()> {
+    crate::engine::with_engine(|engine| {
+        engine.lighting.exposure = ExposureSettings {
+            aperture,
+            shutter_speed,
+            iso,
+        };
+    })
+}
+
 #[pyfunction]
 pub fn light_create(radius: f32, r: u8, g: u8, b: u8, a: u8) -> PyResult<LightId> {
     crate::engine::with_engine(|engine| {
@@ -119,6 +533,46 @@ pub fn light_create(radius: f32, r: u8, g: u8, b: u8, a: u8) -> PyResult<LightId
     })
 }
 
+#[pyfunction]
+#[pyo3(signature = (radius, r, g, b, a, direction_x, direction_y, inner_angle, outer_angle))]
+pub fn spotlight_create(
+    radius: f32,
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+    direction_x: f32,
+    direction_y: f32,
+    inner_angle: f32,
+    outer_angle: f32,
+) -> PyResult<LightId> {
+    crate::engine::with_engine(|engine| {
+        let light = Light::new_spot(
+            radius,
+            [r, g, b, a],
+            [direction_x, direction_y],
+            inner_angle,
+            outer_angle,
+        );
+        engine.lights.insert(light)
+    })
+}
+
+#[pyfunction]
+pub fn directional_light_create(
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+    direction_x: f32,
+    direction_y: f32,
+) -> PyResult<LightId> {
+    crate::engine::with_engine(|engine| {
+        let light = Light::new_directional([r, g, b, a], [direction_x, direction_y]);
+        engine.lights.insert(light)
+    })
+}
+
 #[pyfunction]
 pub fn light_set_color(light: LightId, r: u8, g: u8, b: u8, a: u8) -> PyResult<()> {
     crate::engine::with_engine(|engine| {
@@ -165,6 +619,112 @@ pub fn light_set_flicker(light: LightId, amount: f32, speed: f32) -> PyResult<()
     })?
 }
 
+#[pyfunction]
+pub fn light_set_direction(light: LightId, direction_x: f32, direction_y: f32) -> PyResult<()> {
+    crate::engine::with_engine(|engine| {
+        if let Some(l) = engine.lights.get_mut(light) {
+            match &mut l.kind {
+                LightKind::Spot { direction, .. } | LightKind::Directional { direction } => {
+                    *direction = [direction_x, direction_y];
+                    Ok(())
+                }
+                LightKind::Point => Err(pyo3::exceptions::PyValueError::new_err(
+                    "Light has no direction: only spot and directional lights can be aimed",
+                )),
+            }
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid light ID: {}",
+                light
+            )))
+        }
+    })?
+}
+
+#[pyfunction]
+pub fn light_set_spot_angles(light: LightId, inner_angle: f32, outer_angle: f32) -> PyResult<()> {
+    crate::engine::with_engine(|engine| {
+        if let Some(l) = engine.lights.get_mut(light) {
+            match &mut l.kind {
+                LightKind::Spot {
+                    inner_angle: i,
+                    outer_angle: o,
+                    ..
+                } => {
+                    *i = inner_angle;
+                    *o = outer_angle;
+                    Ok(())
+                }
+                _ => Err(pyo3::exceptions::PyValueError::new_err(
+                    "Light is not a spotlight: no cone angles to set",
+                )),
+            }
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid light ID: {}",
+                light
+            )))
+        }
+    })?
+}
+
+#[pyfunction]
+pub fn light_set_position(light: LightId, x: f32, y: f32) -> PyResult<()> {
+    crate::engine::with_engine(|engine| {
+        if let Some(l) = engine.lights.get_mut(light) {
+            l.x.set(x);
+            l.y.set(y);
+            Ok(())
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid light ID: {}",
+                light
+            )))
+        }
+    })?
+}
+
+#[pyfunction]
+pub fn light_set_enabled(light: LightId, enabled: bool) -> PyResult<()> {
+    crate::engine::with_engine(|engine| {
+        if let Some(l) = engine.lights.get_mut(light) {
+            l.enabled = enabled;
+            Ok(())
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid light ID: {}",
+                light
+            )))
+        }
+    })?
+}
+
+/// Whether a circle of `radius` centered at `(cx, cy)` overlaps the
+/// axis-aligned rectangle `(rx, ry, rw, rh)`
+fn circle_overlaps_rect(cx: f32, cy: f32, radius: f32, rx: f32, ry: f32, rw: f32, rh: f32) -> bool {
+    let closest_x = cx.clamp(rx, rx + rw);
+    let closest_y = cy.clamp(ry, ry + rh);
+    let dx = cx - closest_x;
+    let dy = cy - closest_y;
+    dx * dx + dy * dy <= radius * radius
+}
+
+#[pyfunction]
+pub fn lights_in_region(x: f32, y: f32, w: f32, h: f32) -> PyResult<Vec<LightId>> {
+    crate::engine::with_engine(|engine| {
+        engine
+            .lights
+            .iter()
+            .filter(|(_, light)| {
+                light.enabled
+                    && !matches!(light.kind, LightKind::Directional { .. })
+                    && circle_overlaps_rect(light.x.get(), light.y.get(), light.radius, x, y, w, h)
+            })
+            .map(|(id, _)| id)
+            .collect()
+    })
+}
+
 #[pyfunction]
 pub fn light_free(light: LightId) -> PyResult<()> {
     crate::engine::with_engine(|engine| {
@@ -178,3 +738,62 @@ pub fn light_free(light: LightId) -> PyResult<()> {
         }
     })?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_light_grid_buckets_lights_by_bounding_circle() {
+        let point_a = Light::new(30.0, [255, 255, 255, 255]);
+        let directional_b = Light::new_directional([255, 255, 255, 255], [0.0, -1.0]);
+        let point_c = Light::new(60.0, [255, 255, 255, 255]);
+
+        let lights = [
+            (50.0, 50.0, &point_a),
+            (0.0, 0.0, &directional_b),
+            (350.0, 350.0, &point_c),
+        ];
+
+        let grid = build_light_grid(&lights, (0.0, 0.0, 400.0, 400.0), 100.0);
+
+        assert_eq!(grid.cols, 4);
+        assert_eq!(grid.rows, 4);
+        assert_eq!(grid.cell_offsets.len(), (grid.cols * grid.rows + 1) as usize);
+        assert_eq!(*grid.cell_offsets.last().unwrap() as usize, grid.indices.len());
+        assert!(grid.cell_offsets.windows(2).all(|w| w[0] <= w[1]));
+
+        // Directional lights bypass the grid entirely.
+        assert_eq!(grid.directional, vec![1]);
+
+        // point_a's bounding circle sits fully inside a single near-origin cell.
+        assert_eq!(grid.candidates_at(50.0, 50.0).to_vec(), vec![0]);
+
+        // point_c's bounding circle straddles a cell boundary near the far corner.
+        assert!(grid.candidates_at(350.0, 350.0).to_vec().contains(&2));
+    }
+
+    #[test]
+    fn smoothstep_zero_width_edge_steps_instead_of_nan() {
+        assert_eq!(smoothstep(0.5, 0.5, 0.5), 1.0);
+        assert_eq!(smoothstep(0.5, 0.5, 0.4), 0.0);
+    }
+
+    #[test]
+    fn exposure_default_settings_apply_no_scaling() {
+        let exposure = ExposureSettings::default().exposure();
+        assert!(
+            (exposure - 1.0).abs() < 1e-4,
+            "expected default exposure multiplier of 1.0, got {exposure}"
+        );
+    }
+
+    #[test]
+    fn inverse_square_attenuation_windows_to_near_zero_at_radius() {
+        let radius = 100.0;
+        let near = attenuation_for(0.0, radius, AttenuationModel::InverseSquare);
+        let far = attenuation_for(radius * radius * 0.999, radius, AttenuationModel::InverseSquare);
+        assert!(near > far);
+        assert!(far < 0.01);
+    }
+}